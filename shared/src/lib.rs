@@ -3,6 +3,8 @@ use std::{
     num::NonZeroUsize,
 };
 
+pub mod poll;
+
 /// Default to localhost for IPv4 address.
 pub const DEFAULT_IPV4: &str = "127.0.0.1";
 
@@ -39,51 +41,228 @@ impl ToSocketAddrs for Address {
     }
 }
 
+impl Address {
+    /// Connect to this address, giving up if the handshake does not complete
+    /// within `timeout` so a dead peer cannot hang the caller indefinitely.
+    ///
+    /// # Errors
+    /// * Returns an `io::Error` if the address resolves to nothing, or if the connection fails or times out.
+    pub fn connect_timeout(&self, timeout: std::time::Duration) -> std::io::Result<TcpStream> {
+        let addr = self.to_socket_addrs()?.next().ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::AddrNotAvailable,
+                "address resolved to no socket addresses",
+            )
+        })?;
+        TcpStream::connect_timeout(&addr, timeout)
+    }
+}
+
 /// The error type when reading from a TCP stream with `read_stream(...)`.
 #[derive(Debug)]
 pub enum ReadStreamError {
     /// The TCP stream has been closed.
     ConnectionClosed,
 
+    /// A framed message declared a length greater than `MAX_PAYLOAD_SIZE`.
+    FrameTooLarge,
+
+    /// The read did not complete within the configured timeout.
+    TimedOut,
+
+    /// The socket reported `WouldBlock`, i.e. its readable readiness is drained
+    /// for now. The caller should wait for the next readable event from the
+    /// `poll::Reactor` before reading again rather than spinning.
+    WouldBlock,
+
     /// Error reading from stream. Guaranteed not to be `WouldBlock`.
     IoError(std::io::Error),
 }
 
 /// Helper to read a positive number of bytes from a TCP or safely return an error.
 ///
+/// Intended to be called only after the `poll::Reactor` reports that the
+/// socket is readable; a single read is attempted and `WouldBlock` breaks the
+/// drain instead of busy-waiting.
+///
 /// # Errors
 /// * Returns a `ReadStreamError::ConnectionClosed` if the TCP stream has been closed.
+/// * Returns a `ReadStreamError::WouldBlock` if the socket's readable readiness is drained for now.
 /// * Returns a `ReadStreamError::IoError` if an error occurs while reading from the stream. Guaranteed not to be `WouldBlock`.
 pub fn read_stream(
     stream: &mut TcpStream,
     buf: &mut [u8],
 ) -> Result<NonZeroUsize, ReadStreamError> {
     use std::io::Read as _;
-    loop {
-        let size = match stream.read(buf) {
-            // If we successfully got a number of bytes read, check that it is non-zero.
-            Ok(size) => {
-                match NonZeroUsize::try_from(size) {
-                    // Return the valid buffer size.
-                    Ok(size) => size,
-
-                    // Zero bytes indicates that the TCP stream has been closed.
-                    Err(_) => return Err(ReadStreamError::ConnectionClosed),
-                }
+    match stream.read(buf) {
+        // If we successfully got a number of bytes read, check that it is non-zero.
+        Ok(size) => {
+            match NonZeroUsize::try_from(size) {
+                // Return the valid buffer size.
+                Ok(size) => Ok(size),
+
+                // Zero bytes indicates that the TCP stream has been closed.
+                Err(_) => Err(ReadStreamError::ConnectionClosed),
             }
-            Err(e) => {
-                match e.kind() {
-                    // If we're not ready to read, just continue.
-                    std::io::ErrorKind::WouldBlock => continue,
-
-                    // Otherwise, something went wrong.
-                    _ => return Err(ReadStreamError::IoError(e)),
-                }
+        }
+        Err(e) => {
+            match e.kind() {
+                // Readable readiness is drained; break the drain loop rather than spin.
+                std::io::ErrorKind::WouldBlock => Err(ReadStreamError::WouldBlock),
+
+                // Otherwise, something went wrong.
+                _ => Err(ReadStreamError::IoError(e)),
             }
-        };
+        }
+    }
+}
 
-        return Ok(size);
+/// Half-close the write side of `stream` to signal end-of-data while still
+/// reading the other direction.
+///
+/// A file sender finishes writing, calls this, then keeps reading for the
+/// receiver's acknowledgement. The peer's `read_stream` observes the
+/// write half-close as a zero-byte read and reports `ConnectionClosed` on that
+/// direction, yet the sender's read side stays open to receive the ack. This
+/// lets the two sides perform a clean request/response handshake at the end of
+/// a transfer instead of dropping the whole stream and racing an ambiguous EOF
+/// against a hard connection drop.
+///
+/// # Errors
+/// * Returns an `io::Error` if the shutdown call fails.
+pub fn finish_sending(stream: &TcpStream) -> std::io::Result<()> {
+    stream.shutdown(std::net::Shutdown::Write)
+}
+
+/// Sibling of `read_stream` that bounds how long the read may block by setting
+/// the socket's read timeout before reading once.
+///
+/// A `WouldBlock`/`TimedOut` from the expired timeout is surfaced as
+/// `ReadStreamError::TimedOut` rather than silently retried, letting callers
+/// fail fast on a stalled peer. The socket's previous read timeout is restored
+/// before returning so a stream shared with the non-blocking, reactor-driven
+/// `read_stream` path does not inherit this blocking timeout.
+///
+/// Intended for blocking sockets: on an already non-blocking socket the very
+/// first empty read reports `WouldBlock`, which this maps to `TimedOut` even
+/// though no time elapsed, so callers should not mix this with reactor-managed
+/// sockets.
+///
+/// # Errors
+/// * Returns a `ReadStreamError::ConnectionClosed` if the TCP stream has been closed.
+/// * Returns a `ReadStreamError::TimedOut` if no data arrives within `timeout`.
+/// * Returns a `ReadStreamError::IoError` if setting the timeout or reading fails.
+pub fn read_stream_timeout(
+    stream: &mut TcpStream,
+    buf: &mut [u8],
+    timeout: std::time::Duration,
+) -> Result<NonZeroUsize, ReadStreamError> {
+    use std::io::Read as _;
+    let previous = stream.read_timeout().map_err(ReadStreamError::IoError)?;
+    stream
+        .set_read_timeout(Some(timeout))
+        .map_err(ReadStreamError::IoError)?;
+
+    let result = match stream.read(buf) {
+        Ok(size) => match NonZeroUsize::try_from(size) {
+            Ok(size) => Ok(size),
+            Err(_) => Err(ReadStreamError::ConnectionClosed),
+        },
+        Err(e) => match e.kind() {
+            // An expired read timeout surfaces as either kind depending on platform.
+            std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut => {
+                Err(ReadStreamError::TimedOut)
+            }
+            _ => Err(ReadStreamError::IoError(e)),
+        },
+    };
+
+    // Restore the prior timeout so later reads on this stream are unaffected.
+    stream
+        .set_read_timeout(previous)
+        .map_err(ReadStreamError::IoError)?;
+    result
+}
+
+/// Length of the fixed big-endian `u32` frame header prefixed to every message.
+const FRAME_HEADER_SIZE: usize = std::mem::size_of::<u32>();
+
+/// Read exactly `buf.len()` bytes into `buf`, looping across partial reads.
+///
+/// The framing layer is a blocking record protocol: `stream` must be a blocking
+/// socket, not one of the non-blocking sockets the `poll::Reactor` manages. A
+/// blocking socket without a read timeout never reports `WouldBlock`, so a
+/// `WouldBlock` here means the socket is non-blocking or its read timeout
+/// expired; it is surfaced as an error rather than retried, which would
+/// reintroduce the busy-spin the reactor exists to avoid.
+///
+/// # Errors
+/// * Returns a `ReadStreamError::ConnectionClosed` if the TCP stream closes mid-read.
+/// * Returns a `ReadStreamError::IoError` (kind `Other`) if the socket reports `WouldBlock`.
+/// * Returns a `ReadStreamError::IoError` if an error occurs while reading from the stream.
+fn read_exact(stream: &mut TcpStream, buf: &mut [u8]) -> Result<(), ReadStreamError> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match read_stream(stream, &mut buf[filled..]) {
+            Ok(size) => filled += size.get(),
+            Err(ReadStreamError::WouldBlock) => {
+                return Err(ReadStreamError::IoError(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    "read_message requires a blocking socket",
+                )))
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+/// Read a single length-delimited message from a blocking `stream`: a 4-byte
+/// big-endian `u32` length header followed by exactly that many payload bytes.
+///
+/// This centralizes the `MAX_PAYLOAD_SIZE` enforcement that the constant only
+/// documented, giving callers a reliable record protocol rather than guessing
+/// message boundaries from TCP segment boundaries.
+///
+/// # Errors
+/// * Returns a `ReadStreamError::ConnectionClosed` if the TCP stream closes mid-frame.
+/// * Returns a `ReadStreamError::FrameTooLarge` if the declared length exceeds `MAX_PAYLOAD_SIZE`.
+/// * Returns a `ReadStreamError::IoError` if an error occurs while reading from the stream.
+pub fn read_message(stream: &mut TcpStream) -> Result<Vec<u8>, ReadStreamError> {
+    let mut header = [0u8; FRAME_HEADER_SIZE];
+    read_exact(stream, &mut header)?;
+
+    let length = u32::from_be_bytes(header) as usize;
+    if length > MAX_PAYLOAD_SIZE {
+        return Err(ReadStreamError::FrameTooLarge);
+    }
+
+    let mut payload = vec![0u8; length];
+    read_exact(stream, &mut payload)?;
+    Ok(payload)
+}
+
+/// Write a single length-delimited message, prefixing `payload` with its
+/// 4-byte big-endian `u32` length.
+///
+/// The same `MAX_PAYLOAD_SIZE` cap that `read_message` enforces is checked here
+/// so a sender cannot emit a frame the peer is guaranteed to reject as
+/// `FrameTooLarge`, keeping the record protocol symmetric.
+///
+/// # Errors
+/// * Returns an `io::Error` (kind `InvalidInput`) if `payload` exceeds `MAX_PAYLOAD_SIZE`.
+/// * Returns an `io::Error` if writing the header or payload to the stream fails.
+pub fn write_message(stream: &mut TcpStream, payload: &[u8]) -> std::io::Result<()> {
+    use std::io::Write as _;
+    if payload.len() > MAX_PAYLOAD_SIZE {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "payload exceeds MAX_PAYLOAD_SIZE",
+        ));
     }
+    let header = (payload.len() as u32).to_be_bytes();
+    stream.write_all(&header)?;
+    stream.write_all(payload)
 }
 
 // #[cfg(test)]
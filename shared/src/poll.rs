@@ -0,0 +1,83 @@
+use std::{
+    collections::HashMap, io, net::TcpStream, os::fd::AsRawFd, time::Duration,
+};
+
+use mio::{unix::SourceFd, Events, Interest, Poll, Registry, Token};
+
+/// Default capacity for the reactor's `Events` buffer.
+const EVENTS_CAPACITY: usize = 1024;
+
+/// A readiness reactor wrapping a single `mio::Poll` so the crate can
+/// multiplex many connections off one event loop instead of busy-spinning on
+/// a single socket.
+pub struct Reactor {
+    /// The underlying `mio` poller.
+    poll: Poll,
+
+    /// Reusable buffer filled on each call to `poll(...)`.
+    events: Events,
+
+    /// All registered streams, keyed by the token handed back from `register`.
+    connections: HashMap<Token, TcpStream>,
+
+    /// Monotonic counter used to mint a fresh `Token` per registration.
+    next_token: usize,
+}
+
+impl Reactor {
+    /// Create a reactor with an empty registry.
+    ///
+    /// # Errors
+    /// * Returns the `io::Error` from `Poll::new` if the OS poller cannot be created.
+    pub fn new() -> io::Result<Self> {
+        Ok(Self {
+            poll: Poll::new()?,
+            events: Events::with_capacity(EVENTS_CAPACITY),
+            connections: HashMap::new(),
+            next_token: 0,
+        })
+    }
+
+    /// Register a stream for both read and write readiness, returning the
+    /// `Token` that identifies it in subsequent `poll(...)` events.
+    ///
+    /// The stream is switched to non-blocking mode so that `read_stream` drains
+    /// it edge-aware rather than blocking, and is registered by its raw file
+    /// descriptor so the map can keep handing a plain `std::net::TcpStream` back
+    /// to `read_stream`.
+    ///
+    /// # Errors
+    /// * Returns the `io::Error` from `set_nonblocking` or `Registry::register` if the stream cannot be registered.
+    pub fn register(&mut self, stream: TcpStream) -> io::Result<Token> {
+        stream.set_nonblocking(true)?;
+        let token = Token(self.next_token);
+        self.next_token += 1;
+        self.registry().register(
+            &mut SourceFd(&stream.as_raw_fd()),
+            token,
+            Interest::READABLE | Interest::WRITABLE,
+        )?;
+        self.connections.insert(token, stream);
+        Ok(token)
+    }
+
+    /// Borrow the stream previously registered under `token`, if any.
+    pub fn connection(&mut self, token: Token) -> Option<&mut TcpStream> {
+        self.connections.get_mut(&token)
+    }
+
+    /// Block until at least one connection is ready or `timeout` elapses,
+    /// returning the filled event buffer.
+    ///
+    /// # Errors
+    /// * Returns the `io::Error` from `Poll::poll` if the wait fails.
+    pub fn poll(&mut self, timeout: Option<Duration>) -> io::Result<&Events> {
+        self.poll.poll(&mut self.events, timeout)?;
+        Ok(&self.events)
+    }
+
+    /// The poller's registry, used to (de)register sources.
+    fn registry(&self) -> &Registry {
+        self.poll.registry()
+    }
+}
@@ -0,0 +1,74 @@
+//! Integration coverage for the `poll::Reactor` edge-aware drain path: a
+//! registered socket should only be read after a readable event fires, drain
+//! until `WouldBlock`, and report `ConnectionClosed` once the peer hangs up.
+
+use std::{
+    io::Write as _,
+    net::{TcpListener, TcpStream},
+    time::Duration,
+};
+
+use shared::{poll::Reactor, read_stream, ReadStreamError};
+
+#[test]
+fn reactor_drives_edge_aware_read_stream() {
+    // A loopback listener the client connects to; we register the accepted end.
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind loopback listener");
+    let addr = listener.local_addr().expect("listener address");
+
+    let mut client = TcpStream::connect(addr).expect("connect client");
+    let (server, _) = listener.accept().expect("accept connection");
+
+    let mut reactor = Reactor::new().expect("create reactor");
+    let token = reactor.register(server).expect("register server stream");
+
+    // Send a record and wait for the reactor to report the socket readable.
+    client.write_all(b"hello").expect("write payload");
+    client.flush().expect("flush payload");
+
+    let mut drained = Vec::new();
+    'outer: loop {
+        let events = reactor
+            .poll(Some(Duration::from_secs(5)))
+            .expect("poll reactor");
+        let readable = events
+            .iter()
+            .any(|event| event.token() == token && event.is_readable());
+        if !readable {
+            continue;
+        }
+
+        let stream = reactor.connection(token).expect("registered connection");
+        let mut buf = [0u8; 64];
+        loop {
+            match read_stream(stream, &mut buf) {
+                Ok(size) => drained.extend_from_slice(&buf[..size.get()]),
+                // Readable readiness drained for now; stop spinning on this socket.
+                Err(ReadStreamError::WouldBlock) => break 'outer,
+                Err(e) => panic!("unexpected read error: {e:?}"),
+            }
+        }
+    }
+
+    assert_eq!(drained, b"hello");
+
+    // Closing the client must surface as `ConnectionClosed` on the next read.
+    drop(client);
+    loop {
+        let events = reactor
+            .poll(Some(Duration::from_secs(5)))
+            .expect("poll reactor");
+        if !events
+            .iter()
+            .any(|event| event.token() == token && event.is_readable())
+        {
+            continue;
+        }
+        let stream = reactor.connection(token).expect("registered connection");
+        let mut buf = [0u8; 64];
+        match read_stream(stream, &mut buf) {
+            Err(ReadStreamError::ConnectionClosed) => break,
+            other => panic!("expected ConnectionClosed, got {other:?}"),
+        }
+    }
+}